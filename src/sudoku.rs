@@ -1,214 +1,647 @@
-//------------------------------------------------------------//
-//                    Sudoku Generator                        //
-//                                                            //
-// Generates Sudoku puzzles in the terminal using a backtrack //
-// algorithm. The base struct contains only a single member   //
-// which is a value grid of integers.                         //
-//                                                            //
-//------------------------------------------------------------//       
-
-use rand::seq::SliceRandom;
-use rand::thread_rng;
-use std::fmt;
-
-pub struct Sudoku {
-    values: Vec<Vec<i8>>
-}
-
-impl Sudoku {
-    // Construct a 9x9 grid containing only zeros
-    fn new() -> Sudoku {
-        Sudoku {values: vec![vec![0; 9]; 9]}
-    }
-
-    // Returns a sub-grid of 3x3 at the given coordinate
-    fn slice(&self, row: usize, column: usize) -> Vec<Vec<i8>> {
-        let row_range = (row / 3 as usize * 3)..(row / 3 as usize * 3 + 3);
-        let col_range = (column / 3 as usize * 3)..(column / 3 as usize * 3 + 3);
-        let rows = self.values[row_range].to_vec();
-        rows.iter().map(|x| x[col_range.clone()].to_vec()).collect()
-    }
-
-    // Retrieve the next unfilled square within the grid
-    fn get_next_empty(&self) -> Option<Vec<usize>> {
-        for i in 0..9 {
-            for j in 0..9 {
-                if self.values[i][j] == 0 {
-                    return Some(vec![i, j]);
-                }
-            }
-        }
-        None
-    }
-
-    // Randomly fill the grid using a backtrack algorithm
-    // the algorithm tries to fill the next empty square,
-    // if it fails it returns to the last successful filled
-    // value and retries it with the next candidate value
-    fn rand_fill_grid(&mut self, mut counter: i32) -> bool {
-        let mut elements: Vec<i8> = (1..10).collect();
-        elements.shuffle(&mut thread_rng());
-
-        for number in elements {
-            counter += 1;
-
-            // Add a cap to prevent program running infinitely
-            if counter > 2000 {panic!("Failed to fill grid");}
-
-            let next_cell = match self.get_next_empty() {
-                Some(v) => v,
-                None => {
-                    return true;
-                }
-            };
-
-            // If the value can be placed, do so then attempt the next
-            // fill, if that fails, return to this level and reset the
-            // current cell
-            if self.can_place(next_cell[0], next_cell[1], &number) {
-                self.values[next_cell[0]][next_cell[1]] = number;
-                if self.rand_fill_grid(counter) {
-                    return true;
-                }
-                self.values[next_cell[0]][next_cell[1]] = 0;
-            }
-        }
-        false
-    }
-
-    // Check if the specified value can be placed in the given cell
-    fn can_place(&self, row: usize, column: usize, number: &i8) -> bool {
-        if self.values[row][column] != 0 {return false;}
-        if self.values[row].iter().any(|x| x==number) {return false;}
-        if self.values.iter().map(|x| x[column]).any(|x| x == *number) {
-            return false;
-        }
-        let chunk = self.slice(row, column);
-        let invalid = chunk.iter().any(|x| x.iter().any(|y| y == number));
-
-        !invalid
-    }
-
-    // Mask out the given number of values to convert the filled grid
-    // into a puzzle
-    fn hide_values(&mut self, n_vals: usize) -> () {
-        if n_vals > 81 {
-            panic!("Cannot hide more than max number of values");
-        }
-
-        let mut values: Vec<usize> = (0..81).collect();
-        values.shuffle(&mut thread_rng());
-
-        for i in 0..n_vals {
-            let row = values[i] / 9 as usize;
-            let col = values[i] - row * 9;
-            self.values[row][col] = 0;
-        }
-    }
-}
-
-impl fmt::Display for Sudoku {
-    // Define how the puzzle should be displayed within the terminal
-    // interpret any zeros as values to hide
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let mut out_str = "".to_string();
-        for (i, row) in self.values.iter().enumerate() {
-            if i % 3 == 0 && i > 0 {
-            
-                out_str += "--------- --------- ---------\n";
-            }            
-            for (j, val) in row.iter().enumerate() {
-                if j % 3 == 0 && j > 0 {out_str += "|";}
-                if val.clone() == 0 {
-                    out_str += "   ";
-                } else {
-                    out_str += &format!(" {} ", val).to_string();
-                }
-            }
-            out_str += "\n";
-        }
-        write!(
-            f,
-            "{}",
-            out_str
-        )
-    }
-}
-
-impl Default for Sudoku {
-    // Define a default which is a randomly
-    // generated puzzle
-    fn default() -> Sudoku {
-        let mut out = Sudoku::new();
-        let counter: i32 = 0;
-
-        out.rand_fill_grid(counter);
-        out.hide_values(60);
-
-        out
-    }
-}
-
-pub fn create_puzzle() -> Sudoku {
-    let mut grid = Sudoku::new();
-    grid.rand_fill_grid(0);
-    grid
-}
-
-pub fn solve_puzzle(values: Vec<Vec<i8>>) -> Sudoku {
-    let mut puzzle = Sudoku{values: values};
-    puzzle.rand_fill_grid(0);
-    puzzle 
-}
-
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    #[test]
-    fn test_grid_slice() -> () {
-        let vals = vec![
-            (1..10).collect(),
-            (10..19).collect(),
-            (19..28).collect(),
-            (28..37).collect(),
-            (37..46).collect(),
-            (46..55).collect(),
-            (55..64).collect(),
-            (64..73).collect(),
-            (73..82).collect()
-        ];
-        let grid = Sudoku {values: vals};
-        assert_eq!(grid.slice(3, 3), vec![vec![31,32,33], vec![40,41,42], vec![49,50,51]]);
-    }
-
-    #[test]
-    fn test_can_place() -> () {
-        let mut grid = Sudoku::new();
-        grid.values[2][2] = 7;
-        assert!(grid.can_place(2, 3, &8));
-        assert!(!grid.can_place(2, 3, &7));
-    }
-
-    #[test]
-    fn test_puzzle_gen() -> () {
-        create_puzzle();
-    }
-
-    #[test]
-    fn test_puzzle_solve() -> () {
-        let puzzle_vals = vec![
-            vec![6,8,0,0,5,0,0,3,0],
-            vec![0,0,5,9,0,0,6,0,4],
-            vec![9,4,0,6,3,0,5,2,0],
-            vec![0,7,4,3,0,0,2,5,0],
-            vec![2,6,9,0,4,5,7,0,3],
-            vec![0,5,3,0,0,0,0,4,0],
-            vec![0,1,0,0,6,7,0,9,0],
-            vec![4,0,6,5,0,3,8,0,0],
-            vec![0,0,0,0,1,0,0,6,0]
-        ];
-
-        solve_puzzle(puzzle_vals);
-    }
-}
\ No newline at end of file
+//------------------------------------------------------------//
+//                    Sudoku Generator                        //
+//                                                            //
+// Generates Sudoku puzzles in the terminal using a backtrack //
+// algorithm. The base struct holds the box order and a value //
+// grid of integers.                                          //
+//                                                            //
+//------------------------------------------------------------//
+
+use rand::seq::SliceRandom;
+use rand::thread_rng;
+use std::fmt;
+use std::io::{self, BufRead};
+
+#[derive(Clone)]
+pub struct Sudoku {
+    // Side length of a sub-box; the full grid is order*order on a side.
+    // 3 gives the classic 9x9 grid, 2 gives a 4x4, 4 gives a 16x16.
+    order: usize,
+    values: Vec<Vec<i8>>
+}
+
+impl Sudoku {
+    // Construct a grid of order `order` (e.g. 3 for the classic 9x9)
+    // containing only zeros
+    fn new(order: usize) -> Sudoku {
+        let size = order * order;
+        Sudoku {order: order, values: vec![vec![0; size]; size]}
+    }
+
+    // Number of rows/columns in the grid
+    fn size(&self) -> usize {
+        self.order * self.order
+    }
+
+    // Returns the order*order sub-grid containing the given coordinate
+    fn slice(&self, row: usize, column: usize) -> Vec<Vec<i8>> {
+        let row_range = (row / self.order * self.order)..(row / self.order * self.order + self.order);
+        let col_range = (column / self.order * self.order)..(column / self.order * self.order + self.order);
+        let rows = self.values[row_range].to_vec();
+        rows.iter().map(|x| x[col_range.clone()].to_vec()).collect()
+    }
+
+    // Pick the empty cell with the fewest legal candidates (the
+    // "most constrained variable" heuristic), returning its coordinates
+    // together with those candidates already computed. A cell with zero
+    // candidates is returned immediately so the caller can fail fast
+    // instead of exploring every other empty cell first. This keeps the
+    // branching factor down on larger boards (16x16 and up), where trying
+    // cells in row-major order with no pruning is combinatorially explosive.
+    fn most_constrained_empty(&self) -> Option<(usize, usize, Vec<i8>)> {
+        let mut best: Option<(usize, usize, Vec<i8>)> = None;
+
+        for row in 0..self.size() {
+            for col in 0..self.size() {
+                if self.values[row][col] != 0 {
+                    continue;
+                }
+
+                let candidates: Vec<i8> = (1..=self.size() as i8).filter(|n| self.can_place(row, col, n)).collect();
+
+                if candidates.is_empty() {
+                    return Some((row, col, candidates));
+                }
+
+                let is_better = match &best {
+                    None => true,
+                    Some((_, _, current)) => candidates.len() < current.len()
+                };
+                if is_better {
+                    best = Some((row, col, candidates));
+                }
+            }
+        }
+
+        best
+    }
+
+    // Randomly fill the grid using a backtrack algorithm. Each step fills
+    // the most constrained empty square (see `most_constrained_empty`),
+    // trying its candidates in a shuffled order; if a branch fails it
+    // returns to the last successful fill and retries with the next
+    // candidate value.
+    fn rand_fill_grid(&mut self, mut counter: i32) -> bool {
+        let (row, col, mut candidates) = match self.most_constrained_empty() {
+            Some(v) => v,
+            None => return true
+        };
+        candidates.shuffle(&mut thread_rng());
+
+        // Add a cap, scaled to the grid size, to prevent the program
+        // running infinitely
+        let cap = (self.size() * self.size() * 50) as i32;
+
+        for number in candidates {
+            counter += 1;
+            if counter > cap {panic!("Failed to fill grid");}
+
+            self.values[row][col] = number;
+            if self.rand_fill_grid(counter) {
+                return true;
+            }
+            self.values[row][col] = 0;
+        }
+        false
+    }
+
+    // Check if the specified value can be placed in the given cell
+    fn can_place(&self, row: usize, column: usize, number: &i8) -> bool {
+        if self.values[row][column] != 0 {return false;}
+        if self.values[row].iter().any(|x| x==number) {return false;}
+        if self.values.iter().map(|x| x[column]).any(|x| x == *number) {
+            return false;
+        }
+        let chunk = self.slice(row, column);
+        let invalid = chunk.iter().any(|x| x.iter().any(|y| y == number));
+
+        !invalid
+    }
+
+    // Check that every pre-filled cell is consistent with the row/column/box
+    // rules, ignoring blanks. A given that clashes with another given makes
+    // the puzzle unsolvable before a single cell is guessed.
+    fn givens_valid(&self) -> bool {
+        for row in 0..self.size() {
+            for column in 0..self.size() {
+                let number = self.values[row][column];
+                if number == 0 {
+                    continue;
+                }
+                let mut without_given = self.clone();
+                without_given.values[row][column] = 0;
+                if !without_given.can_place(row, column, &number) {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    // Deterministically fill the grid using a backtrack algorithm, trying
+    // candidates in ascending order rather than shuffled so that the same
+    // starting grid always solves to the same completion.
+    fn backtrack_fill(&mut self) -> bool {
+        let (row, col, candidates) = match self.most_constrained_empty() {
+            Some(v) => v,
+            None => return true
+        };
+
+        for number in candidates {
+            self.values[row][col] = number;
+            if self.backtrack_fill() {
+                return true;
+            }
+            self.values[row][col] = 0;
+        }
+        false
+    }
+
+    // Solve an arbitrary grid, respecting whatever values are already
+    // filled in. Returns `None` if the givens already conflict with each
+    // other or if no completion exists, rather than panicking.
+    pub fn solve(&self) -> Option<Sudoku> {
+        if !self.givens_valid() {
+            return None;
+        }
+
+        let mut attempt = self.clone();
+        if attempt.backtrack_fill() {
+            Some(attempt)
+        } else {
+            None
+        }
+    }
+
+    // Mask out the given number of values to convert the filled grid
+    // into a puzzle
+    fn hide_values(&mut self, n_vals: usize) -> () {
+        let n_cells = self.size() * self.size();
+        if n_vals > n_cells {
+            panic!("Cannot hide more than max number of values");
+        }
+
+        let mut values: Vec<usize> = (0..n_cells).collect();
+        values.shuffle(&mut thread_rng());
+
+        for i in 0..n_vals {
+            let row = values[i] / self.size();
+            let col = values[i] - row * self.size();
+            self.values[row][col] = 0;
+        }
+    }
+
+    // Count completions of the grid, stopping as soon as `limit` is
+    // reached. Used to tell a uniquely-solvable puzzle (count of 1) apart
+    // from an ambiguous one, without needing to enumerate every solution.
+    pub fn count_solutions(&self, limit: usize) -> usize {
+        let mut attempt = self.clone();
+        let mut count = 0;
+        attempt.count_solutions_inner(limit, &mut count);
+        count
+    }
+
+    fn count_solutions_inner(&mut self, limit: usize, count: &mut usize) -> () {
+        if *count >= limit {
+            return;
+        }
+
+        let (row, col, candidates) = match self.most_constrained_empty() {
+            Some(v) => v,
+            None => {
+                *count += 1;
+                return;
+            }
+        };
+
+        for number in candidates {
+            if *count >= limit {
+                return;
+            }
+            self.values[row][col] = number;
+            self.count_solutions_inner(limit, count);
+            self.values[row][col] = 0;
+        }
+    }
+
+    // Parse a grid from a `Read`er, in either the coordinate format (a
+    // `rows,cols` header followed by `row,col,value` triples for each
+    // given cell) or a plain grid where each line is one row and `.`/`0`/
+    // a space mean blank. Unspecified or blank cells are left as 0.
+    pub fn from_reader<R: io::Read>(reader: R) -> Result<Sudoku, String> {
+        let lines: Vec<String> = io::BufReader::new(reader)
+            .lines()
+            .collect::<Result<_, _>>()
+            .map_err(|e| e.to_string())?;
+
+        // Only truly empty lines are skipped here: in the plain grid form a
+        // line of spaces is a row of blanks.
+        let mut lines = lines.into_iter().filter(|l| !l.is_empty());
+        let first = lines.next().ok_or("Input is empty")?;
+
+        match Sudoku::parse_dimensions(&first) {
+            Some((rows, cols)) => {
+                if rows != cols {
+                    return Err(format!("Grid must be square, got {}x{}", rows, cols));
+                }
+                let order = (rows as f64).sqrt().round() as usize;
+                if order * order != rows {
+                    return Err(format!("Grid must have a square number of rows/columns, got {}x{}", rows, cols));
+                }
+                let mut grid = Sudoku::new(order);
+
+                for line in lines {
+                    if line.trim().is_empty() {
+                        continue;
+                    }
+                    let parts: Vec<&str> = line.trim().split(',').collect();
+                    if parts.len() != 3 {
+                        return Err(format!("Expected 'row,col,value', got '{}'", line));
+                    }
+                    let row: usize = parts[0].trim().parse().map_err(|_| format!("Invalid row in '{}'", line))?;
+                    let col: usize = parts[1].trim().parse().map_err(|_| format!("Invalid column in '{}'", line))?;
+                    let value: i8 = parts[2].trim().parse().map_err(|_| format!("Invalid value in '{}'", line))?;
+
+                    if row >= rows || col >= cols {
+                        return Err(format!("Cell ({}, {}) is outside the {}x{} grid", row, col, rows, cols));
+                    }
+                    grid.values[row][col] = value;
+                }
+
+                Ok(grid)
+            },
+            None => {
+                let rows: Vec<String> = std::iter::once(first).chain(lines).collect();
+                let size = rows.len();
+                let order = (size as f64).sqrt().round() as usize;
+                if order * order != size {
+                    return Err(format!("Grid must have a square number of rows, got {}", size));
+                }
+
+                let mut grid = Sudoku::new(order);
+                for (i, line) in rows.iter().enumerate() {
+                    let cells: Vec<char> = line.chars().collect();
+                    if cells.len() != size {
+                        return Err(format!("Row {} has {} cells, expected {}", i, cells.len(), size));
+                    }
+                    for (j, c) in cells.iter().enumerate() {
+                        grid.values[i][j] = match c {
+                            '.' | '0' | ' ' => 0,
+                            d if d.is_ascii_digit() => d.to_digit(10).unwrap() as i8,
+                            other => return Err(format!("Invalid character '{}' at row {} column {}", other, i, j))
+                        };
+                    }
+                }
+
+                Ok(grid)
+            }
+        }
+    }
+
+    pub fn from_str(input: &str) -> Result<Sudoku, String> {
+        Sudoku::from_reader(input.as_bytes())
+    }
+
+    fn parse_dimensions(line: &str) -> Option<(usize, usize)> {
+        let parts: Vec<&str> = line.trim().split(',').collect();
+        if parts.len() != 2 {
+            return None;
+        }
+        let rows: usize = parts[0].trim().parse().ok()?;
+        let cols: usize = parts[1].trim().parse().ok()?;
+        Some((rows, cols))
+    }
+
+    // Serialize the grid back into the coordinate format `from_reader`
+    // accepts, so a puzzle can be saved and reloaded.
+    pub fn to_coord_string(&self) -> String {
+        let mut out = format!("{},{}\n", self.size(), self.size());
+        for row in 0..self.size() {
+            for col in 0..self.size() {
+                if self.values[row][col] != 0 {
+                    out += &format!("{},{},{}\n", row, col, self.values[row][col]);
+                }
+            }
+        }
+        out
+    }
+}
+
+// How many givens a generated puzzle is left with: fewer givens means a
+// harder puzzle to solve by hand. Targets are expressed as a fraction of
+// the classic 9x9's 36/30/26 givens, scaled to whatever order is in use.
+pub enum Difficulty {
+    Easy,
+    Medium,
+    Hard
+}
+
+impl Difficulty {
+    fn target_givens(&self, n_cells: usize) -> usize {
+        // Round instead of truncate so the tiers don't collapse into each
+        // other on small boards, and enforce a minimum one-given gap
+        // between adjacent tiers as a floor for boards too small even for
+        // rounding to keep them apart.
+        let round_div = |numerator: usize, denom: usize| (numerator + denom / 2) / denom;
+
+        let easy = round_div(n_cells * 36, 81);
+        let medium = round_div(n_cells * 30, 81).min(easy.saturating_sub(1));
+        let hard = round_div(n_cells * 26, 81).min(medium.saturating_sub(1));
+
+        match self {
+            Difficulty::Easy => easy,
+            Difficulty::Medium => medium,
+            Difficulty::Hard => hard
+        }
+    }
+}
+
+impl fmt::Display for Sudoku {
+    // Define how the puzzle should be displayed within the terminal
+    // interpret any zeros as values to hide
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let cell_width = self.size().to_string().len() + 2;
+        let mut out_str = "".to_string();
+        for (i, row) in self.values.iter().enumerate() {
+            if i % self.order == 0 && i > 0 {
+                out_str += &format!("{}\n", "-".repeat(cell_width * self.size() + self.order - 1));
+            }
+            for (j, val) in row.iter().enumerate() {
+                if j % self.order == 0 && j > 0 {out_str += "|";}
+                if val.clone() == 0 {
+                    out_str += &" ".repeat(cell_width);
+                } else {
+                    out_str += &format!("{:^width$}", val, width = cell_width);
+                }
+            }
+            out_str += "\n";
+        }
+        write!(
+            f,
+            "{}",
+            out_str
+        )
+    }
+}
+
+impl Default for Sudoku {
+    // Define a default which is a randomly
+    // generated 9x9 puzzle
+    fn default() -> Sudoku {
+        let mut out = Sudoku::new(3);
+        let counter: i32 = 0;
+
+        out.rand_fill_grid(counter);
+        out.hide_values(60);
+
+        out
+    }
+}
+
+pub fn create_puzzle(order: usize) -> Sudoku {
+    let mut grid = Sudoku::new(order);
+    grid.rand_fill_grid(0);
+    grid
+}
+
+pub fn solve_puzzle(values: Vec<Vec<i8>>) -> Option<Sudoku> {
+    let size = values.len();
+    let order = (size as f64).sqrt().round() as usize;
+
+    if order * order != size || values.iter().any(|row| row.len() != size) {
+        return None;
+    }
+
+    Sudoku{order: order, values: values}.solve()
+}
+
+// The largest order `generate_puzzle` will accept. Digging a uniquely-
+// solvable puzzle means re-counting solutions from scratch for every
+// candidate blank, which stays fast through the classic 9x9 (order 3) but
+// becomes intractable well before a 16x16 (order 4): unlike `create_puzzle`
+// or `solve`, which only need one completion and stay fast at any order,
+// proving a *low-clue* grid has exactly one completion is exponentially
+// harder as the board grows.
+pub const MAX_GENERATE_ORDER: usize = 3;
+
+// Generate a puzzle that is guaranteed to have exactly one solution, by
+// starting from a full grid and only blanking a cell when doing so doesn't
+// introduce a second completion. Stops once the chosen difficulty's target
+// number of givens remains.
+//
+// Only supports orders up to `MAX_GENERATE_ORDER`; for a larger board use
+// `create_puzzle`, which fills a full grid without the uniqueness digging
+// that makes larger orders impractical here.
+pub fn generate_puzzle(order: usize, difficulty: Difficulty) -> Sudoku {
+    if order > MAX_GENERATE_ORDER {
+        panic!("generate_puzzle only supports orders up to {} (order {} would need exponentially long uniqueness checks); use create_puzzle for a full grid at any order", MAX_GENERATE_ORDER, order);
+    }
+
+    let mut puzzle = Sudoku::new(order);
+    puzzle.rand_fill_grid(0);
+
+    let n_cells = puzzle.size() * puzzle.size();
+    let mut cells: Vec<usize> = (0..n_cells).collect();
+    cells.shuffle(&mut thread_rng());
+
+    let mut n_givens = n_cells;
+    let target = difficulty.target_givens(n_cells);
+
+    for cell in cells {
+        if n_givens <= target {
+            break;
+        }
+
+        let row = cell / puzzle.size();
+        let col = cell % puzzle.size();
+        let previous = puzzle.values[row][col];
+        puzzle.values[row][col] = 0;
+
+        if puzzle.count_solutions(2) == 1 {
+            n_givens -= 1;
+        } else {
+            puzzle.values[row][col] = previous;
+        }
+    }
+
+    puzzle
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_grid_slice() -> () {
+        let vals = vec![
+            (1..10).collect(),
+            (10..19).collect(),
+            (19..28).collect(),
+            (28..37).collect(),
+            (37..46).collect(),
+            (46..55).collect(),
+            (55..64).collect(),
+            (64..73).collect(),
+            (73..82).collect()
+        ];
+        let grid = Sudoku {order: 3, values: vals};
+        assert_eq!(grid.slice(3, 3), vec![vec![31,32,33], vec![40,41,42], vec![49,50,51]]);
+    }
+
+    #[test]
+    fn test_can_place() -> () {
+        let mut grid = Sudoku::new(3);
+        grid.values[2][2] = 7;
+        assert!(grid.can_place(2, 3, &8));
+        assert!(!grid.can_place(2, 3, &7));
+    }
+
+    #[test]
+    fn test_puzzle_gen() -> () {
+        create_puzzle(3);
+    }
+
+    #[test]
+    fn test_puzzle_gen_order_two() -> () {
+        let grid = create_puzzle(2);
+        assert_eq!(grid.values.len(), 4);
+        assert_eq!(grid.values[0].len(), 4);
+    }
+
+    #[test]
+    fn test_puzzle_solve() -> () {
+        let puzzle_vals = vec![
+            vec![6,8,0,0,5,0,0,3,0],
+            vec![0,0,5,9,0,0,6,0,4],
+            vec![9,4,0,6,3,0,5,2,0],
+            vec![0,7,4,3,0,0,2,5,0],
+            vec![2,6,9,0,4,5,7,0,3],
+            vec![0,5,3,0,0,0,0,4,0],
+            vec![0,1,0,0,6,7,0,9,0],
+            vec![4,0,6,5,0,3,8,0,0],
+            vec![0,0,0,0,1,0,0,6,0]
+        ];
+
+        assert!(solve_puzzle(puzzle_vals).is_some());
+    }
+
+    #[test]
+    fn test_puzzle_solve_rejects_conflicting_givens() -> () {
+        let mut puzzle_vals = vec![vec![0; 9]; 9];
+        puzzle_vals[0][0] = 5;
+        puzzle_vals[0][1] = 5;
+
+        assert!(solve_puzzle(puzzle_vals).is_none());
+    }
+
+    #[test]
+    fn test_puzzle_solve_rejects_non_square_grid() -> () {
+        let puzzle_vals = vec![vec![0; 9]; 10];
+        assert!(solve_puzzle(puzzle_vals).is_none());
+    }
+
+    #[test]
+    fn test_puzzle_solve_rejects_ragged_rows() -> () {
+        let mut puzzle_vals = vec![vec![0; 9]; 9];
+        puzzle_vals[3] = vec![0; 5];
+        assert!(solve_puzzle(puzzle_vals).is_none());
+    }
+
+    #[test]
+    fn test_count_solutions() -> () {
+        let mut grid = Sudoku::new(3);
+        grid.rand_fill_grid(0);
+
+        assert_eq!(grid.count_solutions(2), 1);
+
+        grid.values[0][0] = 0;
+        assert!(grid.count_solutions(2) >= 1);
+    }
+
+    #[test]
+    fn test_generate_puzzle_has_unique_solution() -> () {
+        let puzzle = generate_puzzle(3, Difficulty::Hard);
+
+        let n_givens: usize = puzzle.values.iter().flatten().filter(|v| **v != 0).count();
+        assert!(n_givens >= 26);
+        assert_eq!(puzzle.count_solutions(2), 1);
+    }
+
+    #[test]
+    fn test_generate_puzzle_order_two() -> () {
+        let puzzle = generate_puzzle(2, Difficulty::Easy);
+        assert_eq!(puzzle.count_solutions(2), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "generate_puzzle only supports orders up to 3")]
+    fn test_generate_puzzle_rejects_orders_above_max() -> () {
+        generate_puzzle(MAX_GENERATE_ORDER + 1, Difficulty::Easy);
+    }
+
+    #[test]
+    fn test_difficulty_targets_stay_distinct_at_small_orders() -> () {
+        let n_cells = 2 * 2 * 2 * 2;
+        let easy = Difficulty::Easy.target_givens(n_cells);
+        let medium = Difficulty::Medium.target_givens(n_cells);
+        let hard = Difficulty::Hard.target_givens(n_cells);
+
+        assert!(easy > medium);
+        assert!(medium > hard);
+    }
+
+    #[test]
+    fn test_from_str_coord_format() -> () {
+        let input = "9,9\n0,0,6\n0,1,8\n1,2,5\n";
+        let grid = Sudoku::from_str(input).unwrap();
+        assert_eq!(grid.values[0][0], 6);
+        assert_eq!(grid.values[0][1], 8);
+        assert_eq!(grid.values[1][2], 5);
+        assert_eq!(grid.values[8][8], 0);
+    }
+
+    #[test]
+    fn test_from_str_plain_grid_format() -> () {
+        let input = "\
+6 8 . . 5 . . 3 .
+. . 5 9 . . 6 . 4
+9 4 . 6 3 . 5 2 .
+. 7 4 3 . . 2 5 .
+2 6 9 . 4 5 7 . 3
+. 5 3 . . . . 4 .
+. 1 . . 6 7 . 9 .
+4 . 6 5 . 3 8 . .
+. . . . 1 . . 6 .".replace(' ', "");
+        let grid = Sudoku::from_str(&input).unwrap();
+        assert_eq!(grid.values[0][0], 6);
+        assert_eq!(grid.values[0][2], 0);
+        assert!(grid.solve().is_some());
+    }
+
+    #[test]
+    fn test_from_str_rejects_non_square_dimensions() -> () {
+        let result = Sudoku::from_str("10,10\n9,9,5\n");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_from_str_and_to_coord_string_round_trip() -> () {
+        let puzzle_vals = vec![
+            vec![6,8,0,0,5,0,0,3,0],
+            vec![0,0,5,9,0,0,6,0,4],
+            vec![9,4,0,6,3,0,5,2,0],
+            vec![0,7,4,3,0,0,2,5,0],
+            vec![2,6,9,0,4,5,7,0,3],
+            vec![0,5,3,0,0,0,0,4,0],
+            vec![0,1,0,0,6,7,0,9,0],
+            vec![4,0,6,5,0,3,8,0,0],
+            vec![0,0,0,0,1,0,0,6,0]
+        ];
+        let grid = Sudoku{order: 3, values: puzzle_vals};
+
+        let serialized = grid.to_coord_string();
+        let reloaded = Sudoku::from_str(&serialized).unwrap();
+
+        assert_eq!(reloaded.values, grid.values);
+    }
+}
@@ -0,0 +1,255 @@
+//------------------------------------------------------------//
+//                    Tilt Maze Generator                     //
+//                                                            //
+// Generates gravity/tilt maze puzzles: a ball rolls across a //
+// walled grid in the current gravity direction until it hits //
+// a wall or the edge of the board. The puzzle is solved by   //
+// finding a sequence of clockwise/counter-clockwise board     //
+// rotations that delivers the ball to the exit.               //
+//                                                            //
+//------------------------------------------------------------//
+
+use rand::{thread_rng, Rng};
+use std::collections::{HashSet, VecDeque};
+use std::fmt;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Cell {
+    Open,
+    Wall
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum GravityDirection {
+    Up,
+    Down,
+    Left,
+    Right
+}
+
+impl GravityDirection {
+    fn delta(&self) -> (isize, isize) {
+        match self {
+            GravityDirection::Up => (-1, 0),
+            GravityDirection::Down => (1, 0),
+            GravityDirection::Left => (0, -1),
+            GravityDirection::Right => (0, 1)
+        }
+    }
+
+    fn rotate(&self, rotation: Rotation) -> GravityDirection {
+        match (self, rotation) {
+            (GravityDirection::Up, Rotation::Clockwise) => GravityDirection::Right,
+            (GravityDirection::Right, Rotation::Clockwise) => GravityDirection::Down,
+            (GravityDirection::Down, Rotation::Clockwise) => GravityDirection::Left,
+            (GravityDirection::Left, Rotation::Clockwise) => GravityDirection::Up,
+            (GravityDirection::Up, Rotation::CounterClockwise) => GravityDirection::Left,
+            (GravityDirection::Left, Rotation::CounterClockwise) => GravityDirection::Down,
+            (GravityDirection::Down, Rotation::CounterClockwise) => GravityDirection::Right,
+            (GravityDirection::Right, Rotation::CounterClockwise) => GravityDirection::Up
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Rotation {
+    Clockwise,
+    CounterClockwise
+}
+
+impl fmt::Display for Rotation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Rotation::Clockwise => write!(f, "\u{21bb}"),
+            Rotation::CounterClockwise => write!(f, "\u{21ba}")
+        }
+    }
+}
+
+pub struct TiltMaze {
+    grid: Vec<Vec<Cell>>,
+    ball_start: (usize, usize),
+    exit: (usize, usize)
+}
+
+impl TiltMaze {
+    fn new(width: usize, height: usize, ball_start: (usize, usize), exit: (usize, usize)) -> TiltMaze {
+        TiltMaze{grid: vec![vec![Cell::Open; width]; height], ball_start: ball_start, exit: exit}
+    }
+
+    fn width(&self) -> usize {
+        self.grid[0].len()
+    }
+
+    fn height(&self) -> usize {
+        self.grid.len()
+    }
+
+    // Roll the ball from `from` in `direction` until it hits a wall or
+    // runs off the edge of the board, returning its resting cell.
+    fn roll(&self, from: (usize, usize), direction: GravityDirection) -> (usize, usize) {
+        let mut pos = from;
+        let (dr, dc) = direction.delta();
+
+        loop {
+            let next_row = pos.0 as isize + dr;
+            let next_col = pos.1 as isize + dc;
+
+            if next_row < 0 || next_col < 0 {
+                break;
+            }
+            let (next_row, next_col) = (next_row as usize, next_col as usize);
+
+            if next_row >= self.height() || next_col >= self.width() || self.grid[next_row][next_col] == Cell::Wall {
+                break;
+            }
+            pos = (next_row, next_col);
+        }
+
+        pos
+    }
+
+    // Breadth-first search over `(ball_position, gravity_direction)` states,
+    // trying both rotations from each state, to find the shortest sequence
+    // of rotations that rolls the ball onto the exit cell.
+    pub fn solve(&self, start_gravity: GravityDirection) -> Option<Vec<Rotation>> {
+        let start = (self.ball_start, start_gravity);
+
+        if self.ball_start == self.exit {
+            return Some(Vec::new());
+        }
+
+        let mut visited = HashSet::new();
+        visited.insert(start);
+
+        let mut queue = VecDeque::new();
+        queue.push_back((start, Vec::<Rotation>::new()));
+
+        while let Some(((pos, gravity), path)) = queue.pop_front() {
+            for rotation in [Rotation::Clockwise, Rotation::CounterClockwise] {
+                let next_gravity = gravity.rotate(rotation);
+                let next_pos = self.roll(pos, next_gravity);
+                let next_state = (next_pos, next_gravity);
+
+                if next_pos == self.exit {
+                    let mut solution = path.clone();
+                    solution.push(rotation);
+                    return Some(solution);
+                }
+
+                if visited.insert(next_state) {
+                    let mut next_path = path.clone();
+                    next_path.push(rotation);
+                    queue.push_back((next_state, next_path));
+                }
+            }
+        }
+
+        None
+    }
+}
+
+impl fmt::Display for TiltMaze {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut out_str = "".to_string();
+        for (i, row) in self.grid.iter().enumerate() {
+            for (j, cell) in row.iter().enumerate() {
+                let glyph = if (i, j) == self.ball_start {
+                    'B'
+                } else if (i, j) == self.exit {
+                    'E'
+                } else {
+                    match cell {
+                        Cell::Open => '.',
+                        Cell::Wall => '#'
+                    }
+                };
+                out_str += &format!(" {} ", glyph);
+            }
+            out_str += "\n";
+        }
+        write!(f, "{}", out_str)
+    }
+}
+
+// Carve a random maze and keep retrying until the ball can actually reach
+// the exit, giving up after a generous number of attempts rather than
+// looping forever on an unlucky wall layout.
+pub fn create_puzzle(width: usize, height: usize) -> TiltMaze {
+    let mut rng = thread_rng();
+
+    for _ in 0..500 {
+        let ball_start = (rng.gen_range(0..height), rng.gen_range(0..width));
+
+        let exit = loop {
+            let on_row_edge = rng.gen_bool(0.5);
+            let candidate = if on_row_edge {
+                (if rng.gen_bool(0.5) {0} else {height - 1}, rng.gen_range(0..width))
+            } else {
+                (rng.gen_range(0..height), if rng.gen_bool(0.5) {0} else {width - 1})
+            };
+            if candidate != ball_start {
+                break candidate;
+            }
+        };
+
+        let mut maze = TiltMaze::new(width, height, ball_start, exit);
+
+        for i in 0..height {
+            for j in 0..width {
+                if (i, j) == ball_start || (i, j) == exit {
+                    continue;
+                }
+                if rng.gen_bool(0.2) {
+                    maze.grid[i][j] = Cell::Wall;
+                }
+            }
+        }
+
+        if maze.solve(GravityDirection::Down).is_some() {
+            return maze;
+        }
+    }
+
+    panic!("Failed to generate a solvable tilt maze");
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_create_puzzle_is_solvable() -> () {
+        let maze = create_puzzle(8, 8);
+        assert!(maze.solve(GravityDirection::Down).is_some());
+    }
+
+    #[test]
+    fn test_roll_stops_at_wall() -> () {
+        let mut maze = TiltMaze::new(5, 5, (0, 0), (4, 4));
+        maze.grid[3][0] = Cell::Wall;
+        assert_eq!(maze.roll((0, 0), GravityDirection::Down), (2, 0));
+    }
+
+    #[test]
+    fn test_roll_stops_at_edge() -> () {
+        let maze = TiltMaze::new(5, 5, (0, 0), (4, 4));
+        assert_eq!(maze.roll((0, 0), GravityDirection::Down), (4, 0));
+    }
+
+    #[test]
+    fn test_solve_open_board() -> () {
+        let maze = TiltMaze::new(4, 4, (0, 0), (3, 3));
+        let solution = maze.solve(GravityDirection::Down).unwrap();
+        assert!(!solution.is_empty());
+    }
+
+    #[test]
+    fn test_solve_returns_none_when_unreachable() -> () {
+        let mut maze = TiltMaze::new(3, 3, (0, 0), (2, 2));
+        for i in 0..3 {
+            maze.grid[i][1] = Cell::Wall;
+        }
+        assert!(maze.solve(GravityDirection::Down).is_none());
+    }
+}
@@ -11,7 +11,8 @@ use itertools::Itertools;
 use rand::{thread_rng, Rng, seq::SliceRandom, distributions::{Distribution, Standard}};
 use std::fmt;
 
-enum Direction {
+#[derive(Clone, Copy, Debug)]
+pub enum Direction {
     Down,
     Up,
     Right,
@@ -20,8 +21,18 @@ enum Direction {
     DiagUp
 }
 
+// Where and how a single word ended up on the grid, so an answer key can be
+// printed alongside the puzzle.
+#[derive(Clone, Debug)]
+pub struct Placement {
+    pub word: String,
+    pub start: (usize, usize),
+    pub direction: Direction
+}
+
 pub struct Wordsearch {
-    grid: Vec<Vec<char>>
+    grid: Vec<Vec<char>>,
+    placements: Vec<Placement>
 }
 
 impl Distribution<Direction> for Standard {
@@ -39,7 +50,19 @@ impl Distribution<Direction> for Standard {
 
 impl Wordsearch {
     fn new(width: usize, height: usize) -> Wordsearch {
-        Wordsearch{grid: vec![vec![' '; width]; height]}
+        Wordsearch{grid: vec![vec![' '; width]; height], placements: Vec::new()}
+    }
+
+    // The word, start coordinate and direction for every word that was
+    // successfully placed, in placement order.
+    pub fn placements(&self) -> &Vec<Placement> {
+        &self.placements
+    }
+
+    // An answer-key view of the puzzle: same grid dimensions, but only
+    // cells belonging to a placed word are shown, everything else is blank.
+    pub fn solution(&self) -> WordsearchSolution<'_> {
+        WordsearchSolution{puzzle: self}
     }
     fn valid_directions(&mut self, word: String, row: usize, column: usize) -> Option<Vec<Direction>> {
 
@@ -118,33 +141,91 @@ impl Wordsearch {
             }
         }
     }
+    // Weave a secret message into the cells that are still blank after all
+    // words have been placed, spreading the characters roughly evenly across
+    // the available gaps so they don't clump together. Any cell the message
+    // doesn't land on is left for `fill_blanks` to fill randomly as before.
+    fn embed_secret(&mut self, message: &str) -> Result<(), String> {
+        let mut blanks = Vec::<(usize, usize)>::new();
+        for i in 0..self.grid.len() {
+            for j in 0..self.grid[i].len() {
+                if self.grid[i][j] == ' ' {
+                    blanks.push((i, j));
+                }
+            }
+        }
+
+        let chars: Vec<char> = message.chars().filter(|c| c.is_ascii_alphabetic()).map(|c| c.to_ascii_uppercase()).collect();
+
+        if chars.is_empty() {
+            return Ok(());
+        }
+
+        if chars.len() >= blanks.len() {
+            return Err(format!(
+                "Secret message has {} letters but only {} blank cells are available and it needs more blank cells than letters, enlarge the grid",
+                chars.len(),
+                blanks.len()
+            ));
+        }
+
+        let gap = blanks.len() / chars.len();
+
+        for (i, c) in chars.iter().enumerate() {
+            let offset = thread_rng().gen_range(0..gap);
+            let (row, column) = blanks[i * gap + offset];
+            self.grid[row][column] = *c;
+        }
+
+        Ok(())
+    }
     fn place_word(&mut self, word: &String, row: usize, column: usize, direction: &Direction) -> () {
         if word.len() < 2 || (word.len() > self.grid[0].len() && word.len() > self.grid.len()) {
             panic!("Word '{}' is invalid length for {}x{} puzzle", word, self.grid[0].len(), self.grid.len());
         }
         for (i, c) in word.chars().enumerate() {
-            let coords: Vec<usize> = match direction {
-                Direction::Down => {
-                    vec![row, column + i]
-                },
-                Direction::Up => {
-                    vec![row, column - i]
-                },
-                Direction::Right => {
-                    vec![row + i, column]
-                },
-                Direction::Left => {
-                    vec![row - i, column]
-                },
-                Direction::DiagUp => {
-                    vec![row + i, column - i]
-                },
-                Direction::DiagDown => {
-                    vec![row + i, column + i]
-                }
-            };
-            self.grid[coords[0]][coords[1]] = c;
+            let (r, c_) = Wordsearch::cell_at(row, column, i, direction);
+            self.grid[r][c_] = c;
         }
+        self.placements.push(Placement{word: word.to_string(), start: (row, column), direction: *direction});
+    }
+    // The grid cell a word's i-th character lands on when placed at
+    // (row, column) heading in the given direction.
+    fn cell_at(row: usize, column: usize, i: usize, direction: &Direction) -> (usize, usize) {
+        match direction {
+            Direction::Down => (row, column + i),
+            Direction::Up => (row, column - i),
+            Direction::Right => (row + i, column),
+            Direction::Left => (row - i, column),
+            Direction::DiagUp => (row + i, column - i),
+            Direction::DiagDown => (row + i, column + i)
+        }
+    }
+}
+
+pub struct WordsearchSolution<'a> {
+    puzzle: &'a Wordsearch
+}
+
+impl<'a> fmt::Display for WordsearchSolution<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut visible = vec![vec![' '; self.puzzle.grid[0].len()]; self.puzzle.grid.len()];
+
+        for placement in &self.puzzle.placements {
+            for (i, c) in placement.word.chars().enumerate() {
+                let (r, c_) = Wordsearch::cell_at(placement.start.0, placement.start.1, i, &placement.direction);
+                visible[r][c_] = c;
+            }
+        }
+
+        let mut out_str = "".to_string();
+        for row in visible.iter() {
+            for val in row {
+                out_str += &format!(" {} ", val).to_string();
+            }
+            out_str += "\n";
+        }
+        write!(f, "{}", out_str)
     }
 }
 
@@ -161,7 +242,7 @@ impl fmt::Display for Wordsearch {
     }
 }
 
-pub fn create_puzzle(words: &Vec<String>, width: usize, height: usize) -> Wordsearch {
+pub fn create_puzzle(words: &Vec<String>, width: usize, height: usize, secret: Option<&str>) -> Result<Wordsearch, String> {
     let mut puzzle = Wordsearch::new(width, height);
 
     for word in words {
@@ -182,9 +263,13 @@ pub fn create_puzzle(words: &Vec<String>, width: usize, height: usize) -> Wordse
         }
     }
 
+    if let Some(message) = secret {
+        puzzle.embed_secret(message)?;
+    }
+
     puzzle.fill_blanks();
 
-    puzzle
+    Ok(puzzle)
 }
 
 #[cfg(test)]
@@ -201,6 +286,41 @@ mod test {
             "iron".to_string(),
             "oxide".to_string()
         ];
-        create_puzzle(&word_list, 20, 20);
+        create_puzzle(&word_list, 20, 20, None).unwrap();
+    }
+
+    #[test]
+    fn test_wordsearch_embed_secret() -> () {
+        let word_list = vec![
+            "airspeed".to_string(),
+            "velocity".to_string()
+        ];
+        let puzzle = create_puzzle(&word_list, 20, 20, Some("hello")).unwrap();
+        let flat: String = puzzle.grid.iter().flatten().collect();
+        assert!(flat.contains('H'));
+    }
+
+    #[test]
+    fn test_wordsearch_embed_secret_too_long() -> () {
+        let word_list = vec!["iron".to_string()];
+        let result = create_puzzle(&word_list, 3, 3, Some("this message is far too long to fit"));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_wordsearch_placements_and_solution() -> () {
+        let word_list = vec![
+            "airspeed".to_string(),
+            "velocity".to_string(),
+            "unladen".to_string()
+        ];
+        let puzzle = create_puzzle(&word_list, 20, 20, None).unwrap();
+
+        assert_eq!(puzzle.placements().len(), word_list.len());
+
+        let solution = format!("{}", puzzle.solution());
+        let n_letters: usize = solution.chars().filter(|c| c.is_alphabetic()).count();
+        let max_expected: usize = word_list.iter().map(|w| w.len()).sum();
+        assert!(n_letters > 0 && n_letters <= max_expected);
     }
 }